@@ -1,12 +1,10 @@
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 use media_manager_core::{
-    MediaScanner, MediaRenamer, UndoManager,
-    MediaFile, UndoData,
+    MediaScanner, MediaRenamer, MediaWatcher, Thumbnailer, ThumbnailFormat, UndoManager,
+    MediaFile, UndoData, Config, SubtitleManager,
     MediaManagerError, Result,
 };
-use tokio::sync::Mutex;
-use std::sync::Arc;
 use serde_json::{self, json};
 
 #[derive(Parser, Debug)]
@@ -25,6 +23,20 @@ enum Commands {
         /// Directory to scan (defaults to current directory)
         #[arg(default_value = ".")]
         directory: PathBuf,
+        /// Compute a content hash for each file (extra I/O)
+        #[arg(long)]
+        hash: bool,
+    },
+    /// Prints an OpenSubtitles search URL for a video file
+    SubsUrl {
+        /// Video file to resolve subtitles for
+        input: PathBuf,
+    },
+    /// Reports groups of files with identical content hashes
+    Dedupe {
+        /// Directory to scan (defaults to current directory)
+        #[arg(default_value = ".")]
+        directory: PathBuf,
     },
     /// Stages a rename operation for a file or pattern
     Rename {
@@ -36,37 +48,77 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Watches a directory and reacts to new or modified files
+    Watch {
+        /// Directory to watch (defaults to current directory)
+        #[arg(default_value = ".")]
+        directory: PathBuf,
+        /// Optional template to auto-rename newly detected files
+        #[arg(long)]
+        template: Option<String>,
+    },
+    /// Extracts a thumbnail or contact sheet from a video file
+    Thumbnail {
+        /// Video file to extract frames from
+        input: PathBuf,
+        /// Output directory (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        out_dir: PathBuf,
+        /// Naming template for the generated image
+        #[arg(long, default_value = "{filename}")]
+        template: String,
+        /// Output format: jpeg or webp
+        #[arg(long, default_value = "jpeg")]
+        format: String,
+        /// Single-frame position as a fraction of the duration (0.0-1.0)
+        #[arg(long, default_value_t = 0.25)]
+        position: f64,
+        /// Produce an N-frame contact sheet instead of a single frame
+        #[arg(long)]
+        contact_sheet: Option<usize>,
+    },
     /// Shows currently staged rename operations
     Preview,
     /// Applies all staged rename operations
-    Commit,
+    Commit {
+        /// Finish a previously interrupted commit from its journal
+        #[arg(long)]
+        resume: bool,
+    },
     /// Reverts the last committed rename batch
     Undo,
+    /// Re-applies the most recently undone rename batch
+    Redo,
 }
 
-// Global state for undo data
-type LastUndoData = Arc<Mutex<Option<UndoData>>>;
-
 #[tokio::main]
 async fn main() -> Result<()> {
     env_logger::init(); // Initialize logging
 
     let cli = Cli::parse();
 
-    let scanner = MediaScanner::new();
+    // Configuration drives scan concurrency (and, with the `online` feature,
+    // the enrichment API key).
+    let config = Config::load().await.unwrap_or_default();
+    let scanner = MediaScanner::new_with_concurrency(config.scan_concurrency);
     let renamer = MediaRenamer::new().expect("Failed to initialize MediaRenamer");
     let undo_manager = UndoManager::new()?;
 
-    // Shared state for last undo data
-    let last_undo_data: LastUndoData = Arc::new(Mutex::new(undo_manager.load_undo_data().await?));
-
     match &cli.command {
         Commands::Status => {
             handle_status_command().await?;
         }
-        Commands::Scan { directory } => {
+        Commands::Scan { directory, hash } => {
+            let scanner = scanner.with_content_hashing(*hash);
             handle_scan_command(&scanner, directory).await?;
         }
+        Commands::SubsUrl { input } => {
+            handle_subs_url_command(&config, input).await?;
+        }
+        Commands::Dedupe { directory } => {
+            let scanner = scanner.with_content_hashing(true);
+            handle_dedupe_command(&scanner, directory).await?;
+        }
         Commands::Rename { file_or_pattern, template, dry_run } => {
             handle_rename_command(
                 &scanner,
@@ -76,18 +128,23 @@ async fn main() -> Result<()> {
                 *dry_run,
             ).await?;
         }
+        Commands::Watch { directory, template } => {
+            handle_watch_command(directory, template.as_deref()).await?;
+        }
+        Commands::Thumbnail { input, out_dir, template, format, position, contact_sheet } => {
+            handle_thumbnail_command(input, out_dir, template, format, *position, *contact_sheet).await?;
+        }
         Commands::Preview => {
             handle_preview_command(&renamer).await?;
         }
-        Commands::Commit => {
-            handle_commit_command(
-                &renamer,
-                &undo_manager,
-                last_undo_data.clone(),
-            ).await?;
+        Commands::Commit { resume } => {
+            handle_commit_command(&renamer, &undo_manager, *resume).await?;
         }
         Commands::Undo => {
-            handle_undo_command(&renamer, &undo_manager, last_undo_data.clone()).await?;
+            handle_undo_command(&renamer, &undo_manager).await?;
+        }
+        Commands::Redo => {
+            handle_redo_command(&renamer, &undo_manager).await?;
         }
     }
 
@@ -97,7 +154,8 @@ async fn main() -> Result<()> {
 // --- CLI Command Handlers (could be moved to cli_commands.rs for larger projects) ---
 
 async fn handle_scan_command(scanner: &MediaScanner, directory: &PathBuf) -> Result<()> {
-    let media_files = scanner.scan_directory(directory).await?;
+    let report = scanner.scan_directory_reported(directory).await?;
+    let media_files = report.files;
 
     // Convert MediaFile objects to JSON-serializable format
     let files_json: Vec<serde_json::Value> = media_files.iter().map(|file| {
@@ -106,6 +164,7 @@ async fn handle_scan_command(scanner: &MediaScanner, directory: &PathBuf) -> Res
                 json!({
                     "index": stream.index,
                     "language": stream.language,
+                    "lang_code": stream.lang_code,
                     "title": stream.title,
                     "codec": stream.codec
                 })
@@ -131,6 +190,7 @@ async fn handle_scan_command(scanner: &MediaScanner, directory: &PathBuf) -> Res
 
         json!({
             "path": file.path.to_string_lossy(),
+            "content_hash": file.content_hash,
             "metadata": metadata_json
         })
     }).collect();
@@ -138,6 +198,8 @@ async fn handle_scan_command(scanner: &MediaScanner, directory: &PathBuf) -> Res
     let result = json!({
         "files": files_json,
         "count": media_files.len(),
+        "metadata_errors": report.errors.len(),
+        "skipped_non_video": report.skipped.len(),
         "directory": directory.to_string_lossy()
     });
 
@@ -151,39 +213,103 @@ async fn handle_scan_command(scanner: &MediaScanner, directory: &PathBuf) -> Res
     Ok(())
 }
 
+async fn handle_subs_url_command(config: &Config, input: &PathBuf) -> Result<()> {
+    if !input.exists() {
+        return Err(MediaManagerError::FileNotFound(input.clone()));
+    }
+    let manager = SubtitleManager::new().await?;
+    let url = manager.resolve_opensubtitles_url(input, config).await;
+    println!("{}", url);
+    Ok(())
+}
+
+async fn handle_dedupe_command(scanner: &MediaScanner, directory: &PathBuf) -> Result<()> {
+    use std::collections::BTreeMap;
+
+    let report = scanner.scan_directory_reported(directory).await?;
+
+    // Group by content hash across every scanned file, video or not, so the
+    // report reflects everything that was actually hashed.
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for file in report.files.iter().chain(report.skipped.iter()) {
+        if let Some(hash) = &file.content_hash {
+            groups.entry(hash.clone()).or_default().push(file.path.to_string_lossy().into_owned());
+        }
+    }
+    let duplicates: Vec<serde_json::Value> = groups
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(hash, paths)| json!({ "hash": hash, "paths": paths }))
+        .collect();
+
+    let result = json!({
+        "directory": directory.to_string_lossy(),
+        "duplicate_groups": duplicates.len(),
+        "groups": duplicates,
+    });
+
+    let mut output = serde_json::to_string_pretty(&result)?;
+    output = output.replace("\r\n", "\n");
+    output = output.trim().to_string();
+    output.push('\n');
+
+    print!("{}", output);
+    Ok(())
+}
+
 async fn handle_rename_command(
-    _scanner: &MediaScanner,  // Will be used for pattern matching in future
+    _scanner: &MediaScanner,
     renamer: &MediaRenamer,
     file_or_pattern: &str,
     template: &str,
     dry_run: bool,
 ) -> Result<()> {
-    // For prototype, assume file_or_pattern is a single file path for now
-    // Future: implement pattern matching for batch rename
-    let file_path = PathBuf::from(file_or_pattern);
-
-    if !file_path.exists() {
-        return Err(MediaManagerError::FileNotFound(file_path));
-    }
+    // A glob pattern expands to many files; a plain path stays a batch of one.
+    let paths = if is_glob_pattern(file_or_pattern) {
+        let mut matched: Vec<PathBuf> = glob::glob(file_or_pattern)
+            .map_err(|e| MediaManagerError::Unknown(format!("invalid pattern '{}': {}", file_or_pattern, e)))?
+            .filter_map(|entry| entry.ok())
+            .filter(|p| p.is_file())
+            .collect();
+        // Stable ordering so `{index}` numbering is deterministic.
+        matched.sort();
+        if matched.is_empty() {
+            return Err(MediaManagerError::FileNotFound(PathBuf::from(file_or_pattern)));
+        }
+        matched
+    } else {
+        let file_path = PathBuf::from(file_or_pattern);
+        if !file_path.exists() {
+            return Err(MediaManagerError::FileNotFound(file_path));
+        }
+        vec![file_path]
+    };
 
-    let mut media_file = MediaFile::new(file_path.clone());
-    // Get metadata for the single file
-    match media_manager_core::metadata::MediaMetadata::from_file(&media_file.path).await {
-        Ok(metadata) => media_file.metadata = Some(metadata),
-        Err(e) => log::warn!("Could not get metadata for {}: {}", media_file.path.display(), e),
+    // Probe each matched file; metadata failures stay non-fatal as elsewhere.
+    let mut media_files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let mut media_file = MediaFile::new(path);
+        match media_manager_core::metadata::MediaMetadata::from_file(&media_file.path).await {
+            Ok(metadata) => media_file.metadata = Some(metadata),
+            Err(e) => log::warn!("Could not get metadata for {}: {}", media_file.path.display(), e),
+        }
+        media_files.push(media_file);
     }
 
+    let total = media_files.len();
     if dry_run {
-        let preview = renamer.preview_rename(&media_file, template)?;
         println!("Rename preview (dry run):");
-        println!("From: '{}'", preview.original_path.display());
-        println!("To:   '{}'", preview.new_path.display());
-        if !preview.is_valid {
-            println!("Warning: {}", preview.validation_message.unwrap_or_else(|| "Invalid rename operation".to_string()));
+        for (idx, media_file) in media_files.iter().enumerate() {
+            let preview = renamer.preview_rename(media_file, template, idx + 1, total)?;
+            println!("From: '{}'", preview.original_path.display());
+            println!("To:   '{}'", preview.new_path.display());
+            if !preview.is_valid {
+                println!("Warning: {}", preview.validation_message.unwrap_or_else(|| "Invalid rename operation".to_string()));
+            }
         }
     } else {
-        renamer.stage_single_rename(&media_file, template).await?;
-        println!("Staged rename operation:");
+        renamer.stage_batch_rename(&media_files, template).await?;
+        println!("Staged {} rename operation(s):", total);
         handle_preview_command(renamer).await?; // Show preview immediately
         println!("Run 'commit' to apply, or 'rename' again to change.");
     }
@@ -191,6 +317,55 @@ async fn handle_rename_command(
     Ok(())
 }
 
+/// Returns true when `pattern` contains shell wildcard metacharacters and so
+/// should be expanded via `glob` rather than treated as a literal path.
+fn is_glob_pattern(pattern: &str) -> bool {
+    pattern.contains('*') || pattern.contains('?') || pattern.contains('[')
+}
+
+async fn handle_watch_command(directory: &PathBuf, template: Option<&str>) -> Result<()> {
+    let watcher = MediaWatcher::new(directory)?;
+    log::info!("Watching {}", watcher.directory().display());
+
+    // Emit each event as a single JSON line so the stream can be piped.
+    watcher
+        .watch(template, |event| match serde_json::to_string(&event) {
+            Ok(line) => println!("{}", line),
+            Err(e) => log::error!("Failed to serialize watch event: {}", e),
+        })
+        .await
+}
+
+async fn handle_thumbnail_command(
+    input: &PathBuf,
+    out_dir: &PathBuf,
+    template: &str,
+    format: &str,
+    position: f64,
+    contact_sheet: Option<usize>,
+) -> Result<()> {
+    if !input.exists() {
+        return Err(MediaManagerError::FileNotFound(input.clone()));
+    }
+
+    let format = match format.to_lowercase().as_str() {
+        "jpeg" | "jpg" => ThumbnailFormat::Jpeg,
+        "webp" => ThumbnailFormat::Webp,
+        other => {
+            return Err(MediaManagerError::Unknown(format!("unknown thumbnail format '{}'", other)));
+        }
+    };
+
+    let thumbnailer = Thumbnailer::new()?;
+    let out_path = match contact_sheet {
+        Some(count) => thumbnailer.contact_sheet(input, out_dir, template, format, count).await?,
+        None => thumbnailer.single_frame(input, out_dir, template, format, position).await?,
+    };
+
+    println!("Wrote thumbnail to '{}'", out_path.display());
+    Ok(())
+}
+
 async fn handle_preview_command(renamer: &MediaRenamer) -> Result<()> {
     let staged_renames = renamer.load_staged_renames().await?;
     if staged_renames.is_empty() {
@@ -212,15 +387,23 @@ async fn handle_preview_command(renamer: &MediaRenamer) -> Result<()> {
 async fn handle_commit_command(
     renamer: &MediaRenamer,
     undo_manager: &UndoManager,
-    last_undo_data: LastUndoData,
+    resume: bool,
 ) -> Result<()> {
-    let committed_ops = renamer.commit_renames().await?;
-    println!("Committing {} staged renames...", committed_ops.len());
+    // Report live progress as each move lands.
+    let progress = |current: usize, total: usize, path: &std::path::Path| {
+        println!("[{}/{}] {}", current, total, path.display());
+    };
+
+    let committed_ops = if resume {
+        println!("Resuming interrupted commit...");
+        renamer.resume_commit_with_progress(progress).await?
+    } else {
+        renamer.commit_renames_with_progress(progress).await?
+    };
 
-    // Save undo data
+    // Push the batch onto the undo/redo history
     let new_undo_data = UndoData { operations: committed_ops };
-    undo_manager.save_undo_data(&new_undo_data).await?;
-    *last_undo_data.lock().await = Some(new_undo_data);
+    undo_manager.push_batch(new_undo_data).await?;
 
     println!("Rename operations committed successfully!");
     Ok(())
@@ -262,13 +445,10 @@ async fn handle_status_command() -> Result<()> {
 async fn handle_undo_command(
     renamer: &MediaRenamer,
     undo_manager: &UndoManager,
-    last_undo_data: LastUndoData,
 ) -> Result<()> {
-    let mut last_undo_data_lock = last_undo_data.lock().await;
-    if let Some(undo_data) = last_undo_data_lock.take() { // Take the data to prevent double undo
+    if let Some(undo_data) = undo_manager.pop_undo().await? {
         println!("Attempting to undo last batch of {} renames...", undo_data.operations.len());
         renamer.undo_renames(undo_data.operations).await?;
-        undo_manager.clear_undo_data().await?; // Clear the undo file after successful undo
         println!("Last rename batch undone successfully!");
     } else {
         println!("No previous rename batch to undo.");
@@ -276,4 +456,18 @@ async fn handle_undo_command(
     Ok(())
 }
 
+async fn handle_redo_command(
+    renamer: &MediaRenamer,
+    undo_manager: &UndoManager,
+) -> Result<()> {
+    if let Some(redo_data) = undo_manager.pop_redo().await? {
+        println!("Attempting to redo batch of {} renames...", redo_data.operations.len());
+        renamer.redo_renames(redo_data.operations).await?;
+        println!("Rename batch redone successfully!");
+    } else {
+        println!("No rename batch to redo.");
+    }
+    Ok(())
+}
+
 // Subtitle command handlers removed in simplified version
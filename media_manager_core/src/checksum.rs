@@ -0,0 +1,54 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::error::Result;
+
+/// Files at or above this size are hashed partially (head + tail + length)
+/// instead of in full, to keep scanning large video libraries affordable.
+const PARTIAL_HASH_THRESHOLD: u64 = 256 * 1024 * 1024;
+
+/// Number of bytes sampled from each of the head and tail for a partial hash.
+const PARTIAL_CHUNK: usize = 1024 * 1024;
+
+/// Computes a content hash for `path`.
+///
+/// Small files are hashed in full with BLAKE3; files at or above
+/// [`PARTIAL_HASH_THRESHOLD`] get a fast partial hash over their first and last
+/// [`PARTIAL_CHUNK`] bytes plus their length, which is enough to spot duplicate
+/// rips without streaming gigabytes. The returned hex string is prefixed with
+/// the hashing mode so full and partial hashes never collide.
+pub fn hash_file(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+
+    if len < PARTIAL_HASH_THRESHOLD {
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let read = file.read(&mut buf)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buf[..read]);
+        }
+        return Ok(format!("full:{}", hasher.finalize().to_hex()));
+    }
+
+    // Partial hash: head, tail and length. The file is at least
+    // `PARTIAL_HASH_THRESHOLD` bytes here, so each `read_exact` fully fills its
+    // `PARTIAL_CHUNK` buffer — a single `read` could return a short count and
+    // quietly sample fewer bytes than the doc promises.
+    let mut hasher = blake3::Hasher::new();
+    let mut head = vec![0u8; PARTIAL_CHUNK];
+    file.read_exact(&mut head)?;
+    hasher.update(&head);
+
+    let tail_start = len.saturating_sub(PARTIAL_CHUNK as u64);
+    file.seek(SeekFrom::Start(tail_start))?;
+    let mut tail = vec![0u8; PARTIAL_CHUNK];
+    file.read_exact(&mut tail)?;
+    hasher.update(&tail);
+
+    hasher.update(&len.to_le_bytes());
+    Ok(format!("partial:{}", hasher.finalize().to_hex()))
+}
@@ -6,12 +6,22 @@ use crate::error::Result;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
-    // Simplified config without API key management
+    /// Optional API key used by the online enrichment subsystem.
+    /// Absent by default so the tool runs fully offline.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Maximum number of files to probe concurrently during a scan.
+    /// Defaults to the number of available CPUs.
+    #[serde(default = "crate::scanner::default_concurrency")]
+    pub scan_concurrency: usize,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self {}
+        Self {
+            api_key: None,
+            scan_concurrency: crate::scanner::default_concurrency(),
+        }
     }
 }
 
@@ -48,7 +58,20 @@ impl Config {
         Ok(())
     }
 
-    // API key management removed in simplified version
+    /// Returns the API key to use for online enrichment.
+    ///
+    /// The `MEDIA_MANAGER_API_KEY` environment variable takes precedence over
+    /// the on-disk value, mirroring the `MEDIA_MANAGER_CONFIG_DIR` override
+    /// used for the config path. Returns `None` when neither is set, which
+    /// keeps the tool on its offline path.
+    pub fn api_key(&self) -> Option<String> {
+        if let Ok(key) = std::env::var("MEDIA_MANAGER_API_KEY") {
+            if !key.is_empty() {
+                return Some(key);
+            }
+        }
+        self.api_key.clone()
+    }
 
     fn get_config_path() -> Result<PathBuf> {
         // Check for environment variable override (used in testing)
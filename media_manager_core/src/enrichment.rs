@@ -0,0 +1,147 @@
+//! Optional online metadata enrichment.
+//!
+//! This whole module is gated behind the `online` cargo feature. When the
+//! feature is off the rest of the crate keeps running in its fully offline
+//! mode; when it is on, [`MetadataEnricher`] resolves a [`ParsedTitle`] against
+//! a metadata provider (TMDB) to recover the canonical title, release year and
+//! the IMDB/TMDB ids; the IMDB id is what
+//! [`crate::subtitles::SubtitleManager::resolve_opensubtitles_url`] uses to
+//! build a subtitle search URL.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MediaManagerError, Result};
+use crate::parser::{ParsedTitle, TitleKind};
+
+/// Canonical metadata resolved from an online provider.
+///
+/// Any optional field may be absent when the provider returns a partial record
+/// (`title` falls back to the parsed title); callers
+/// should treat a missing `imdb_id` the same as the offline flow (no `tt` id).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrichedMetadata {
+    pub title: String,
+    pub year: Option<u16>,
+    pub imdb_id: Option<String>,
+    pub tmdb_id: Option<u64>,
+}
+
+/// Resolves parsed filenames against TMDB using a REST API key.
+///
+/// Construct it with [`MetadataEnricher::new`] once the key has been read from
+/// [`crate::config::Config::api_key`] (which honours the
+/// `MEDIA_MANAGER_API_KEY` override); if no key is present the caller should
+/// stay on the offline path rather than build an enricher at all.
+#[derive(Debug, Clone)]
+pub struct MetadataEnricher {
+    api_key: String,
+    client: reqwest::Client,
+}
+
+impl MetadataEnricher {
+    const API_BASE: &'static str = "https://api.themoviedb.org/3";
+
+    /// Creates an enricher bound to `api_key`.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Resolves `parsed` to canonical metadata.
+    ///
+    /// Returns `Ok(None)` when the provider has no match for the title, so a
+    /// miss degrades to the offline flow rather than erroring; network and
+    /// decoding failures surface as [`MediaManagerError::Enrichment`].
+    pub async fn enrich(&self, parsed: &ParsedTitle) -> Result<Option<EnrichedMetadata>> {
+        let search_kind = match parsed.kind {
+            TitleKind::Episode => "tv",
+            TitleKind::Movie => "movie",
+        };
+
+        let mut query = vec![
+            ("api_key", self.api_key.clone()),
+            ("query", parsed.title.clone()),
+        ];
+        if let Some(year) = parsed.year {
+            // TMDB names the year parameter differently for film and TV.
+            let key = if search_kind == "tv" {
+                "first_air_date_year"
+            } else {
+                "year"
+            };
+            query.push((key, year.to_string()));
+        }
+
+        let search_url = format!("{}/search/{}", Self::API_BASE, search_kind);
+        let search: SearchResponse = self
+            .client
+            .get(&search_url)
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| MediaManagerError::Enrichment(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| MediaManagerError::Enrichment(e.to_string()))?;
+
+        let Some(top) = search.results.into_iter().next() else {
+            return Ok(None);
+        };
+
+        let canonical_title = top.title.or(top.name).unwrap_or_else(|| parsed.title.clone());
+        let year = top
+            .release_date
+            .or(top.first_air_date)
+            .and_then(|d| d.get(0..4).and_then(|s| s.parse::<u16>().ok()))
+            .or(parsed.year);
+
+        let imdb_id = self.fetch_imdb_id(search_kind, top.id).await?;
+
+        Ok(Some(EnrichedMetadata {
+            title: canonical_title,
+            year,
+            imdb_id,
+            tmdb_id: Some(top.id),
+        }))
+    }
+
+    /// Looks up the IMDB id for a resolved TMDB record via its external ids.
+    async fn fetch_imdb_id(&self, kind: &str, tmdb_id: u64) -> Result<Option<String>> {
+        let url = format!("{}/{}/{}/external_ids", Self::API_BASE, kind, tmdb_id);
+        let external: ExternalIds = self
+            .client
+            .get(&url)
+            .query(&[("api_key", &self.api_key)])
+            .send()
+            .await
+            .map_err(|e| MediaManagerError::Enrichment(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| MediaManagerError::Enrichment(e.to_string()))?;
+
+        Ok(external.imdb_id.filter(|id| !id.is_empty()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<SearchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    id: u64,
+    // Movies carry `title`/`release_date`; TV carries `name`/`first_air_date`.
+    title: Option<String>,
+    name: Option<String>,
+    release_date: Option<String>,
+    first_air_date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExternalIds {
+    imdb_id: Option<String>,
+}
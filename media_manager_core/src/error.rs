@@ -12,6 +12,10 @@ pub enum MediaManagerError {
     FfprobeError(String),
     #[error("ffprobe not found. Please ensure it's installed and in your PATH.")]
     FfprobeNotFound,
+    #[error("ffmpeg command failed: {0}")]
+    FfmpegError(String),
+    #[error("ffmpeg not found. Please ensure it's installed and in your PATH.")]
+    FfmpegNotFound,
     #[error("File not found: {0}")]
     FileNotFound(PathBuf),
     #[error("Invalid template: {0}")]
@@ -28,6 +32,9 @@ pub enum MediaManagerError {
     Unknown(String),
     #[error("Subtitle error: {0}")]
     Subtitle(#[from] SubtitleError),
+    #[cfg(feature = "online")]
+    #[error("metadata enrichment failed: {0}")]
+    Enrichment(String),
 }
 
 pub type Result<T> = std::result::Result<T, MediaManagerError>;
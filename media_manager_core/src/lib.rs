@@ -1,17 +1,30 @@
 pub mod media_file;
+pub mod parser;
+pub mod locale;
 pub mod metadata;
 pub mod scanner;
+pub mod watcher;
+pub mod thumbnailer;
 pub mod renamer;
 pub mod undo;
 pub mod error;
+pub mod checksum;
 pub mod subtitles;
 pub mod config;
+#[cfg(feature = "online")]
+pub mod enrichment;
 
 pub use media_file::{MediaFile, MediaFileType};
+pub use parser::{ParsedTitle, TitleKind};
+pub use locale::{Lang, normalize_language, detect_language_from_title};
 pub use metadata::MediaMetadata;
-pub use scanner::MediaScanner;
+pub use scanner::{MediaScanner, ScanReport, ScanError};
+pub use watcher::{MediaWatcher, WatchEvent};
+pub use thumbnailer::{Thumbnailer, ThumbnailFormat};
 pub use renamer::{MediaRenamer, StagedRename};
-pub use undo::{UndoManager, UndoData};
+pub use undo::{UndoManager, UndoData, UndoHistory};
 pub use error::{MediaManagerError, Result};
-pub use subtitles::{SubtitleManager, SubtitleMetadata, SubtitleError};
+pub use subtitles::{SubtitleManager, SubtitleMetadata, SubtitleExtraction, SubtitleError};
 pub use config::Config;
+#[cfg(feature = "online")]
+pub use enrichment::{EnrichedMetadata, MetadataEnricher};
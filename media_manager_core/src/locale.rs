@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+/// A normalized ISO 639-1 language code (two letters, e.g. `en`, `es`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lang(String);
+
+impl Lang {
+    /// Returns the two-letter language code.
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the `Lang`, returning the owned code string.
+    pub fn into_code(self) -> String {
+        self.0
+    }
+}
+
+/// Maps raw language tokens (ISO 639-1/2 codes and English names) to their
+/// canonical two-letter code. Ordered so that longer, more specific names are
+/// matched before bare codes.
+const LANG_TABLE: &[(&str, &str)] = &[
+    ("english", "en"),
+    ("spanish", "es"),
+    ("castilian", "es"),
+    ("french", "fr"),
+    ("german", "de"),
+    ("italian", "it"),
+    ("hindi", "hi"),
+    ("arabic", "ar"),
+    ("japanese", "ja"),
+    // Three-letter ISO 639-2 variants.
+    ("eng", "en"),
+    ("spa", "es"),
+    ("fra", "fr"),
+    ("ger", "de"),
+    ("deu", "de"),
+    // Bare two-letter codes.
+    ("en", "en"),
+    ("es", "es"),
+    ("fr", "fr"),
+    ("de", "de"),
+    ("it", "it"),
+    ("hi", "hi"),
+    ("ar", "ar"),
+    ("ja", "ja"),
+];
+
+/// Normalizes an ffprobe language tag to a canonical ISO 639-1 code.
+///
+/// Handles the common inconsistencies seen in the wild: bare codes (`eng`,
+/// `en`), English names (`English`) and names carrying a descriptor such as
+/// `English (SDH)`.
+pub fn normalize_language(raw: &str) -> Option<Lang> {
+    let mut cleaned = raw.trim().to_lowercase();
+    // Drop a trailing descriptor, e.g. "english (sdh)" -> "english".
+    if let Some(idx) = cleaned.find('(') {
+        cleaned.truncate(idx);
+    }
+    let cleaned = cleaned.trim();
+
+    for (token, code) in LANG_TABLE {
+        if cleaned == *token {
+            return Some(Lang(code.to_string()));
+        }
+    }
+    None
+}
+
+/// Detects a language from a trailing descriptor embedded in a parsed title,
+/// e.g. `My Movie english` or `My Movie spanish-sdh`.
+///
+/// A trailing `-dub`/`-sdh` marker is stripped first, then the remaining text
+/// is tested against the known language tokens.
+pub fn detect_language_from_title(title: &str) -> Option<Lang> {
+    let lower = title.to_lowercase();
+    let trimmed = lower
+        .trim_end_matches("-dub")
+        .trim_end_matches("-sdh")
+        .trim();
+
+    for (token, code) in LANG_TABLE {
+        // Skip bare two-letter codes here: they are too short to be a reliable
+        // trailing marker inside a free-form title.
+        if token.len() < 3 {
+            continue;
+        }
+        if trimmed == *token || trimmed.ends_with(&format!(" {}", token)) {
+            return Some(Lang(code.to_string()));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_language() {
+        assert_eq!(normalize_language("eng").unwrap().code(), "en");
+        assert_eq!(normalize_language("en").unwrap().code(), "en");
+        assert_eq!(normalize_language("English").unwrap().code(), "en");
+        assert_eq!(normalize_language("English (SDH)").unwrap().code(), "en");
+        assert_eq!(normalize_language("castilian").unwrap().code(), "es");
+        assert!(normalize_language("klingon").is_none());
+    }
+
+    #[test]
+    fn test_detect_language_from_title() {
+        assert_eq!(detect_language_from_title("My Movie english").unwrap().code(), "en");
+        assert_eq!(detect_language_from_title("My Movie spanish-sdh").unwrap().code(), "es");
+        assert!(detect_language_from_title("My Movie").is_none());
+    }
+}
@@ -1,11 +1,13 @@
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use crate::metadata::MediaMetadata;
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum MediaFileType {
     Video,
     Audio,
-    // Add other types as needed
+    Image,
+    Unknown,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -15,6 +17,8 @@ pub struct MediaFile {
     pub extension: String, // e.g., "mp4"
     pub file_type: MediaFileType,
     pub metadata: Option<MediaMetadata>, // Option because ffprobe might fail or not be run
+    /// Content hash, populated only when hashing is enabled for a scan.
+    pub content_hash: Option<String>,
 }
 
 impl MediaFile {
@@ -32,8 +36,11 @@ impl MediaFile {
 
         let file_type = match extension.to_lowercase().as_str() {
             "mp4" | "mkv" | "avi" | "mov" | "webm" => MediaFileType::Video,
-            // Add more extensions for audio, images, etc.
-            _ => MediaFileType::Video, // Default for now, refine later
+            "mp3" | "flac" | "ogg" | "m4a" | "wav" => MediaFileType::Audio,
+            "jpg" | "jpeg" | "png" | "webp" => MediaFileType::Image,
+            // Extension is missing or unrecognized: fall back to sniffing the
+            // file's magic bytes rather than blindly assuming video.
+            _ => Self::sniff_file_type(&path).unwrap_or(MediaFileType::Unknown),
         };
 
         MediaFile {
@@ -42,6 +49,7 @@ impl MediaFile {
             extension,
             file_type,
             metadata: None,
+            content_hash: None,
         }
     }
 
@@ -49,4 +57,26 @@ impl MediaFile {
     pub fn full_filename(&self) -> String {
         format!("{}.{}", self.filename, self.extension)
     }
+
+    /// Sniffs the media type from the file's leading bytes using the `infer`
+    /// crate, bucketing the inferred MIME type into a [`MediaFileType`].
+    ///
+    /// Returns `None` when the file can't be read or its type can't be
+    /// recognized, letting the caller default to [`MediaFileType::Unknown`].
+    fn sniff_file_type(path: &Path) -> Option<MediaFileType> {
+        let mut buf = [0u8; 8192];
+        let mut file = std::fs::File::open(path).ok()?;
+        let read = file.read(&mut buf).ok()?;
+        let kind = infer::get(&buf[..read])?;
+        let mime = kind.mime_type();
+        if mime.starts_with("video/") {
+            Some(MediaFileType::Video)
+        } else if mime.starts_with("audio/") {
+            Some(MediaFileType::Audio)
+        } else if mime.starts_with("image/") {
+            Some(MediaFileType::Image)
+        } else {
+            None
+        }
+    }
 }
@@ -9,6 +9,8 @@ pub struct SubtitleStream {
     pub language: Option<String>,
     pub title: Option<String>,
     pub codec: String,  // "srt", "ass", "pgs", etc.
+    /// Normalized ISO 639-1 code derived from `language`/`title`, if resolvable.
+    pub lang_code: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +47,29 @@ impl MediaMetadata {
         }
     }
 
+    /// Checks if ffmpeg is available on the system.
+    pub async fn check_ffmpeg() -> Result<()> {
+        let output = Command::new("ffmpeg")
+            .arg("-version")
+            .output()
+            .await
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    MediaManagerError::FfmpegNotFound
+                } else {
+                    MediaManagerError::Io(e)
+                }
+            })?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(MediaManagerError::FfmpegError(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ))
+        }
+    }
+
     /// Invokes ffprobe as a subprocess to extract metadata.
     pub async fn from_file(file_path: &Path) -> Result<Self> {
         let output = Command::new("ffprobe")
@@ -109,11 +134,24 @@ impl MediaMetadata {
                             .unwrap_or("unknown")
                             .to_string();
                             
+                        // Normalize the language tag to a two-letter code,
+                        // falling back to any descriptor carried in the title.
+                        let lang_code = language
+                            .as_deref()
+                            .and_then(crate::locale::normalize_language)
+                            .or_else(|| {
+                                title
+                                    .as_deref()
+                                    .and_then(crate::locale::detect_language_from_title)
+                            })
+                            .map(|lang| lang.into_code());
+
                         subtitle_streams.push(SubtitleStream {
                             index,
                             language,
                             title,
                             codec,
+                            lang_code,
                         });
                     }
                 }
@@ -0,0 +1,177 @@
+use std::sync::OnceLock;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Whether a parsed filename looks like a standalone movie or a TV episode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TitleKind {
+    Movie,
+    Episode,
+}
+
+/// Structured metadata recovered from a media file's raw filename stem.
+///
+/// This is the piece that lets template-based renaming key off the real
+/// `{title}`, `{year}`, `{season:02}` and `{episode:02}` rather than the
+/// noisy on-disk name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedTitle {
+    pub title: String,
+    pub year: Option<u16>,
+    pub season: Option<u16>,
+    pub episode: Option<u16>,
+    pub kind: TitleKind,
+}
+
+/// Release/encoding tokens that mark the end of the human-readable title.
+/// Everything from the first of these onwards is treated as junk.
+const JUNK_TOKENS: &[&str] = &[
+    "2160p", "1080p", "720p", "480p", "x264", "x265", "h264", "h265", "hevc",
+    "bluray", "brrip", "bdrip", "webrip", "web-dl", "webdl", "hdtv", "dvdrip",
+    "xvid", "ddp5", "dd5", "aac", "ac3", "proper", "repack",
+];
+
+/// Year detector, compiled once and shared across calls.
+fn year_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b(19|20)\d{2}\b").unwrap())
+}
+
+/// Episode markers in priority order, compiled once and shared across calls.
+///
+/// The `NxNN` pattern is flanked by non-digit boundaries so resolution tokens
+/// like `1920x1080` aren't misread as a season/episode pair.
+fn episode_regexes() -> &'static [Regex] {
+    static RES: OnceLock<Vec<Regex>> = OnceLock::new();
+    RES.get_or_init(|| {
+        [
+            r"(?i)[Ss](\d{1,2})[Ee](\d{1,2})",
+            r"(?i)(?:^|[^0-9])(\d{1,2})x(\d{1,2})(?:[^0-9]|$)",
+            r"(?i)Season\s*(\d+).*Episode\s*(\d+)",
+        ]
+        .iter()
+        .map(|p| Regex::new(p).unwrap())
+        .collect()
+    })
+}
+
+impl ParsedTitle {
+    /// Parses a filename (stem or full name) into structured title metadata
+    /// using a small regex cascade: normalize separators, look for episode
+    /// markers in priority order, detect a year, and strip trailing release
+    /// junk to recover the leading title text.
+    pub fn from_filename(filename: &str) -> Self {
+        let normalized = normalize_separators(filename);
+
+        let year = year_regex()
+            .find(&normalized)
+            .and_then(|m| m.as_str().parse::<u16>().ok());
+
+        for re in episode_regexes() {
+            if let Some(caps) = re.captures(&normalized) {
+                let season = caps.get(1).and_then(|m| m.as_str().parse::<u16>().ok());
+                let episode = caps.get(2).and_then(|m| m.as_str().parse::<u16>().ok());
+                let match_start = caps.get(0).map(|m| m.start()).unwrap_or(normalized.len());
+                let title = clean_title(&normalized[..match_start]);
+
+                return ParsedTitle {
+                    title,
+                    year,
+                    season,
+                    episode,
+                    kind: TitleKind::Episode,
+                };
+            }
+        }
+
+        // No episode markers: treat as a movie and cut the title at the year.
+        let title = match year {
+            Some(y) => {
+                let needle = y.to_string();
+                match normalized.find(&needle) {
+                    Some(idx) => clean_title(&normalized[..idx]),
+                    None => clean_title(&normalized),
+                }
+            }
+            None => clean_title(&normalized),
+        };
+
+        ParsedTitle {
+            title,
+            year,
+            season: None,
+            episode: None,
+            kind: TitleKind::Movie,
+        }
+    }
+}
+
+/// Replaces `.`/`_` and runs of whitespace with single spaces.
+fn normalize_separators(raw: &str) -> String {
+    let replaced: String = raw
+        .chars()
+        .map(|c| if c == '.' || c == '_' { ' ' } else { c })
+        .collect();
+    replaced.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Trims a candidate title at the first junk token and removes trailing
+/// separator characters left behind (e.g. a dangling `(` before a year).
+fn clean_title(candidate: &str) -> String {
+    let lower = candidate.to_lowercase();
+    let mut cut = candidate.len();
+    for token in JUNK_TOKENS {
+        if let Some(idx) = lower.find(token) {
+            // Only treat it as junk on a word boundary.
+            let before_ok = idx == 0 || !lower.as_bytes()[idx - 1].is_ascii_alphanumeric();
+            if before_ok && idx < cut {
+                cut = idx;
+            }
+        }
+    }
+
+    candidate[..cut]
+        .trim()
+        .trim_end_matches(|c: char| c == '(' || c == '-' || c == '[' || c.is_whitespace())
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_episode_sxxexx() {
+        let parsed = ParsedTitle::from_filename("The.Show.S01E02.1080p.mkv");
+        assert_eq!(parsed.title, "The Show");
+        assert_eq!(parsed.season, Some(1));
+        assert_eq!(parsed.episode, Some(2));
+        assert_eq!(parsed.kind, TitleKind::Episode);
+    }
+
+    #[test]
+    fn test_parse_movie_with_year() {
+        let parsed = ParsedTitle::from_filename("Movie Title (2019) BluRay.mp4");
+        assert_eq!(parsed.title, "Movie Title");
+        assert_eq!(parsed.year, Some(2019));
+        assert_eq!(parsed.kind, TitleKind::Movie);
+    }
+
+    #[test]
+    fn test_parse_episode_nxnn() {
+        let parsed = ParsedTitle::from_filename("Series 1x05 WEBRip.mkv");
+        assert_eq!(parsed.title, "Series");
+        assert_eq!(parsed.season, Some(1));
+        assert_eq!(parsed.episode, Some(5));
+        assert_eq!(parsed.kind, TitleKind::Episode);
+    }
+
+    #[test]
+    fn test_resolution_not_parsed_as_episode() {
+        let parsed = ParsedTitle::from_filename("Movie.1920x1080.mkv");
+        assert_eq!(parsed.kind, TitleKind::Movie);
+        assert_eq!(parsed.season, None);
+        assert_eq!(parsed.episode, None);
+    }
+}
@@ -1,4 +1,6 @@
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use regex::Regex;
 use tokio::fs;
 use serde_json;
 use crate::media_file::MediaFile;
@@ -21,13 +23,17 @@ pub struct StagedRename {
 
 pub struct MediaRenamer {
     staged_renames_path: PathBuf,
+    journal_path: PathBuf,
 }
 
 impl MediaRenamer {
     pub fn new() -> Result<Self> {
-        let mut staged_renames_path = std::env::temp_dir();
+        let temp_dir = std::env::temp_dir();
+        let mut staged_renames_path = temp_dir.clone();
         staged_renames_path.push("media_manager_staged_renames.json");
-        Ok(MediaRenamer { staged_renames_path })
+        let mut journal_path = temp_dir;
+        journal_path.push("media_manager_commit_journal.json");
+        Ok(MediaRenamer { staged_renames_path, journal_path })
     }
 
     /// Loads staged renames from the temporary file
@@ -57,13 +63,27 @@ impl MediaRenamer {
 
     /// Generates a new filename based on a template and media file metadata.
     /// Example template: "{filename} [{resolution}]"
-    pub fn apply_template(&self, media_file: &MediaFile, template: &str) -> Result<String> {
+    ///
+    /// `position`/`total` describe where this file sits in a sorted batch and
+    /// drive the `{index}`/`{index:0N}` placeholders, so a whole season can be
+    /// numbered consistently. For a single-file rename pass `(1, 1)`.
+    pub fn apply_template(
+        &self,
+        media_file: &MediaFile,
+        template: &str,
+        position: usize,
+        total: usize,
+    ) -> Result<String> {
         let mut new_name = template.to_string();
 
         // Replace basic placeholders
         new_name = new_name.replace("{filename}", &media_file.filename);
         new_name = new_name.replace("{extension}", &media_file.extension);
 
+        // Ordered-sequence placeholder: `{index}` pads to the width of the
+        // batch size, `{index:0N}` pads to the explicit width N.
+        new_name = Self::apply_index_placeholder(&new_name, position, total);
+
         // Replace metadata placeholders if metadata is available
         if let Some(metadata) = &media_file.metadata {
             if let Some(width) = metadata.width {
@@ -93,11 +113,37 @@ impl MediaRenamer {
         Ok(new_name)
     }
 
+    /// Expands the `{index}` / `{index:0N}` placeholders against a file's
+    /// 1-based `position` within a batch of `total` files.
+    fn apply_index_placeholder(name: &str, position: usize, total: usize) -> String {
+        // Explicit-width form first, e.g. `{index:03}` -> `007`. The regex is
+        // compiled once and shared so a large batch doesn't rebuild it per file.
+        static EXPLICIT: OnceLock<Regex> = OnceLock::new();
+        let explicit = EXPLICIT.get_or_init(|| Regex::new(r"\{index:0(\d+)\}").unwrap());
+        let result = explicit
+            .replace_all(name, |caps: &regex::Captures| {
+                let width: usize = caps[1].parse().unwrap_or(0);
+                format!("{:0width$}", position, width = width)
+            })
+            .into_owned();
+
+        // Bare `{index}` pads to the width of the batch size so the whole set
+        // lines up (e.g. 1..=10 -> `01`..`10`).
+        let default_width = total.to_string().len();
+        result.replace("{index}", &format!("{:0width$}", position, width = default_width))
+    }
+
     /// Stages a single rename operation.
     /// This function generates the `StagedRename` struct and saves it to the temporary file.
     /// Previews a rename operation without staging it
-    pub fn preview_rename(&self, media_file: &MediaFile, template: &str) -> Result<RenamePreview> {
-        let new_filename_stem = match self.apply_template(media_file, template) {
+    pub fn preview_rename(
+        &self,
+        media_file: &MediaFile,
+        template: &str,
+        position: usize,
+        total: usize,
+    ) -> Result<RenamePreview> {
+        let new_filename_stem = match self.apply_template(media_file, template, position, total) {
             Ok(name) => name,
             Err(e) => return Ok(RenamePreview {
                 original_path: media_file.path.clone(),
@@ -130,21 +176,53 @@ impl MediaRenamer {
     }
 
     pub async fn stage_single_rename(&self, media_file: &MediaFile, template: &str) -> Result<()> {
-        let new_filename_stem = self.apply_template(media_file, template)?;
-        let new_filename = format!("{}.{}", new_filename_stem, media_file.extension);
+        self.stage_batch_rename(std::slice::from_ref(media_file), template).await
+    }
 
-        let original_dir = media_file.path.parent().ok_or_else(|| {
-            MediaManagerError::Unknown(format!("Could not get parent directory for {}", media_file.path.display()))
-        })?;
-        let new_path = original_dir.join(&new_filename);
+    /// Stages a whole batch of files against one template, assigning
+    /// `{index}` values in the order the files are passed in (callers should
+    /// sort first). Detects collisions both against targets that already exist
+    /// on disk and against other entries in the same batch before writing
+    /// anything to the staging file.
+    pub async fn stage_batch_rename(&self, media_files: &[MediaFile], template: &str) -> Result<()> {
+        let total = media_files.len();
+        let mut batch = Vec::with_capacity(total);
+        let mut seen_targets: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
 
-        let staged_rename = StagedRename {
-            original_path: media_file.path.clone(),
-            new_path,
-        };
+        for (idx, media_file) in media_files.iter().enumerate() {
+            let new_filename_stem = self.apply_template(media_file, template, idx + 1, total)?;
+            let new_filename = format!("{}.{}", new_filename_stem, media_file.extension);
+
+            let original_dir = media_file.path.parent().ok_or_else(|| {
+                MediaManagerError::Unknown(format!("Could not get parent directory for {}", media_file.path.display()))
+            })?;
+            let new_path = original_dir.join(&new_filename);
+
+            // Collision with a pre-existing file other than this file's own source.
+            if new_path.exists() && new_path != media_file.path {
+                return Err(MediaManagerError::RenameFailed(
+                    media_file.path.clone(),
+                    new_path,
+                    "target path already exists".to_string(),
+                ));
+            }
+            // Collision with another rename in the same batch.
+            if !seen_targets.insert(new_path.clone()) {
+                return Err(MediaManagerError::RenameFailed(
+                    media_file.path.clone(),
+                    new_path,
+                    "target path collides with another file in the batch".to_string(),
+                ));
+            }
+
+            batch.push(StagedRename {
+                original_path: media_file.path.clone(),
+                new_path,
+            });
+        }
 
         let mut staged_renames = self.load_staged_renames().await?;
-        staged_renames.push(staged_rename);
+        staged_renames.extend(batch);
         self.save_staged_renames(&staged_renames).await?;
 
         Ok(())
@@ -153,26 +231,161 @@ impl MediaRenamer {
     /// Applies a batch of staged renames.
     /// Returns a vector of `RenameOperation` for undo purposes.
     pub async fn commit_renames(&self) -> Result<Vec<RenameOperation>> {
+        self.commit_renames_with_progress(|_, _, _| {}).await
+    }
+
+    /// Applies staged renames as a journaled job.
+    ///
+    /// Each successful move is appended to an on-disk journal *as it happens*,
+    /// so an interrupted or mid-batch-failed commit can be finished later with
+    /// [`MediaRenamer::resume_commit`] or unwound with
+    /// [`MediaRenamer::rollback_commit`]. `progress` is invoked as
+    /// `(current, total, path)` after each applied move so a UI can show live
+    /// progress. On failure the journal is left in place for recovery rather
+    /// than aborting silently.
+    pub async fn commit_renames_with_progress<F>(&self, progress: F) -> Result<Vec<RenameOperation>>
+    where
+        F: FnMut(usize, usize, &std::path::Path),
+    {
+        if self.journal_path.exists() {
+            return Err(MediaManagerError::Unknown(
+                "an interrupted commit journal exists; run `commit --resume` or roll it back first"
+                    .to_string(),
+            ));
+        }
+
         let staged_renames = self.load_staged_renames().await?;
         if staged_renames.is_empty() {
             return Err(MediaManagerError::NoStagedRenames);
         }
 
-        let mut committed_operations = Vec::new();
+        let mut applied = Vec::new();
+        self.save_journal(&applied).await?;
+        self.apply_entries(&staged_renames, &mut applied, progress).await?;
+
+        // Success: the journal and staging file are no longer needed.
+        self.clear_journal().await?;
+        self.clear_staged_renames().await?;
+        Ok(applied)
+    }
+
+    /// Finishes an interrupted commit using the journal left behind by a
+    /// previous [`MediaRenamer::commit_renames_with_progress`] run. The
+    /// already-applied moves are read from the journal and only the remaining
+    /// staged entries are executed. Returns the full set of operations
+    /// (recovered + newly applied) so the caller can hand them to the
+    /// `UndoManager`.
+    pub async fn resume_commit(&self) -> Result<Vec<RenameOperation>> {
+        self.resume_commit_with_progress(|_, _, _| {}).await
+    }
+
+    /// [`MediaRenamer::resume_commit`] with a progress callback.
+    pub async fn resume_commit_with_progress<F>(&self, progress: F) -> Result<Vec<RenameOperation>>
+    where
+        F: FnMut(usize, usize, &std::path::Path),
+    {
+        let mut applied = self.load_journal().await?;
+        let staged_renames = self.load_staged_renames().await?;
+        if staged_renames.is_empty() && applied.is_empty() {
+            return Err(MediaManagerError::NoStagedRenames);
+        }
+
+        // Skip entries already recorded in the journal.
+        let done: std::collections::HashSet<PathBuf> =
+            applied.iter().map(|op| op.original_path.clone()).collect();
+        let remaining: Vec<StagedRename> = staged_renames
+            .into_iter()
+            .filter(|sr| !done.contains(&sr.original_path))
+            .collect();
+
+        self.apply_entries(&remaining, &mut applied, progress).await?;
+
+        self.clear_journal().await?;
+        self.clear_staged_renames().await?;
+        Ok(applied)
+    }
 
-        for staged_rename in staged_renames {
+    /// Rolls back the moves recorded in a leftover commit journal, reversing
+    /// each `new_path -> original_path` in reverse order, then clears the
+    /// journal. Returns the operations that were undone.
+    pub async fn rollback_commit(&self) -> Result<Vec<RenameOperation>> {
+        let applied = self.load_journal().await?;
+        for op in applied.iter().rev() {
+            log::info!(
+                "Rolling back '{}' to '{}'",
+                op.new_path.display(),
+                op.original_path.display()
+            );
+            fs::rename(&op.new_path, &op.original_path).await.map_err(|e| {
+                MediaManagerError::RenameFailed(
+                    op.new_path.clone(),
+                    op.original_path.clone(),
+                    e.to_string(),
+                )
+            })?;
+        }
+        self.clear_journal().await?;
+        Ok(applied)
+    }
+
+    /// Applies a slice of staged renames, journaling each successful move and
+    /// reporting progress. Shared by the initial commit and the resume path.
+    async fn apply_entries<F>(
+        &self,
+        entries: &[StagedRename],
+        applied: &mut Vec<RenameOperation>,
+        mut progress: F,
+    ) -> Result<()>
+    where
+        F: FnMut(usize, usize, &std::path::Path),
+    {
+        let total = applied.len() + entries.len();
+        for staged_rename in entries {
             log::info!(
                 "Attempting to rename '{}' to '{}'",
                 staged_rename.original_path.display(),
                 staged_rename.new_path.display()
             );
+
+            // If the target already exists, compare content hashes: skip the
+            // move with a warning when it's a byte-identical duplicate rather
+            // than failing (and aborting) the whole batch.
+            if staged_rename.new_path.exists()
+                && staged_rename.new_path != staged_rename.original_path
+            {
+                match (
+                    crate::checksum::hash_file(&staged_rename.original_path),
+                    crate::checksum::hash_file(&staged_rename.new_path),
+                ) {
+                    (Ok(src), Ok(dst)) if src == dst => {
+                        log::warn!(
+                            "Skipping '{}': identical content already present at '{}'",
+                            staged_rename.original_path.display(),
+                            staged_rename.new_path.display()
+                        );
+                        continue;
+                    }
+                    _ => {
+                        return Err(MediaManagerError::RenameFailed(
+                            staged_rename.original_path.clone(),
+                            staged_rename.new_path.clone(),
+                            "target path already exists with different content".to_string(),
+                        ));
+                    }
+                }
+            }
+
             match fs::rename(&staged_rename.original_path, &staged_rename.new_path).await {
                 Ok(_) => {
                     log::info!("Successfully renamed.");
-                    committed_operations.push(RenameOperation {
-                        original_path: staged_rename.original_path,
-                        new_path: staged_rename.new_path,
+                    applied.push(RenameOperation {
+                        original_path: staged_rename.original_path.clone(),
+                        new_path: staged_rename.new_path.clone(),
                     });
+                    // Journal the move as soon as it lands so an interruption
+                    // after this point is recoverable.
+                    self.save_journal(applied).await?;
+                    progress(applied.len(), total, &staged_rename.new_path);
                 }
                 Err(e) => {
                     log::error!(
@@ -181,19 +394,76 @@ impl MediaRenamer {
                         staged_rename.new_path.display(),
                         e
                     );
-                    // Decide whether to stop on first error or continue
+                    // Leave the journal in place so the commit can be resumed
+                    // or rolled back after the caller resolves the problem.
                     return Err(MediaManagerError::RenameFailed(
-                        staged_rename.original_path,
-                        staged_rename.new_path,
+                        staged_rename.original_path.clone(),
+                        staged_rename.new_path.clone(),
                         e.to_string(),
                     ));
                 }
             }
         }
+        Ok(())
+    }
 
-        // Clear staged renames after successful commit
-        self.clear_staged_renames().await?;
-        Ok(committed_operations)
+    /// Loads the commit journal, returning an empty list when none exists.
+    async fn load_journal(&self) -> Result<Vec<RenameOperation>> {
+        if !self.journal_path.exists() {
+            return Ok(Vec::new());
+        }
+        let json_data = fs::read_to_string(&self.journal_path).await?;
+        Ok(serde_json::from_str(&json_data)?)
+    }
+
+    /// Writes the commit journal to disk.
+    async fn save_journal(&self, operations: &[RenameOperation]) -> Result<()> {
+        let json_data = serde_json::to_string_pretty(operations)?;
+        fs::write(&self.journal_path, json_data).await?;
+        Ok(())
+    }
+
+    /// Removes the commit journal if present.
+    async fn clear_journal(&self) -> Result<()> {
+        if self.journal_path.exists() {
+            fs::remove_file(&self.journal_path).await?;
+        }
+        Ok(())
+    }
+
+    /// Re-applies a batch of rename operations in the forward direction
+    /// (original_path -> new_path), used to redo a previously undone batch.
+    pub async fn redo_renames(&self, operations: Vec<RenameOperation>) -> Result<()> {
+        if operations.is_empty() {
+            return Err(MediaManagerError::NoUndoData);
+        }
+
+        for op in operations {
+            log::info!(
+                "Attempting to redo rename: '{}' to '{}'",
+                op.original_path.display(),
+                op.new_path.display()
+            );
+            match fs::rename(&op.original_path, &op.new_path).await {
+                Ok(_) => {
+                    log::info!("Successfully redone.");
+                }
+                Err(e) => {
+                    log::error!(
+                        "Failed to redo rename '{}' to '{}': {}",
+                        op.original_path.display(),
+                        op.new_path.display(),
+                        e
+                    );
+                    return Err(MediaManagerError::RenameFailed(
+                        op.original_path,
+                        op.new_path,
+                        e.to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Reverts a batch of rename operations.
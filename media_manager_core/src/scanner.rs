@@ -1,56 +1,161 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs;
+use tokio::sync::Semaphore;
 use async_recursion::async_recursion; // For recursive async functions
-use crate::media_file::MediaFile;
+use crate::media_file::{MediaFile, MediaFileType};
 use crate::metadata::MediaMetadata;
 use crate::error::Result;
 
+/// A per-file metadata-extraction failure. These are non-critical: a scan
+/// completes and returns the files it could read alongside the errors it hit.
+#[derive(Debug, Clone)]
+pub struct ScanError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+/// The outcome of a scan: the discovered video files, the non-video files that
+/// were classified but skipped, and any non-fatal metadata extraction failures
+/// so callers can report how many files failed probing.
+#[derive(Debug, Default)]
+pub struct ScanReport {
+    pub files: Vec<MediaFile>,
+    pub skipped: Vec<MediaFile>,
+    pub errors: Vec<ScanError>,
+}
+
 pub struct MediaScanner {
-    // Configurable allowed extensions
-    allowed_extensions: Vec<String>,
+    // Maximum number of ffprobe invocations to run at once.
+    concurrency: usize,
+    // Whether to compute a content hash for each file during scanning.
+    hash_content: bool,
 }
 
 impl MediaScanner {
     pub fn new() -> Self {
+        Self::new_with_concurrency(default_concurrency())
+    }
+
+    /// Creates a scanner that probes at most `concurrency` files at a time.
+    /// A value of zero is treated as one to keep the pool functional.
+    pub fn new_with_concurrency(concurrency: usize) -> Self {
         MediaScanner {
-            allowed_extensions: vec![
-                "mp4".to_string(),
-                "mkv".to_string(),
-                "avi".to_string(),
-                "mov".to_string(),
-                "webm".to_string(),
-            ],
+            concurrency: concurrency.max(1),
+            hash_content: false,
         }
     }
 
-    /// Recursively scans a directory for media files.
-    #[async_recursion]
+    /// Enables or disables content hashing during scans. Hashing is opt-in
+    /// because it costs extra I/O on top of the ffprobe pass.
+    pub fn with_content_hashing(mut self, hash_content: bool) -> Self {
+        self.hash_content = hash_content;
+        self
+    }
+
+    /// Recursively scans a directory for video files, probing metadata
+    /// concurrently. Returns just the video files; non-video files and metadata
+    /// failures are dropped (use [`MediaScanner::scan_directory_reported`] to
+    /// inspect them).
     pub async fn scan_directory(&self, path: &Path) -> Result<Vec<MediaFile>> {
-        let mut media_files = Vec::new();
-        let mut entries = fs::read_dir(path).await?;
+        Ok(self.scan_directory_reported(path).await?.files)
+    }
+
+    /// Recursively scans a directory, collecting candidate paths first and then
+    /// probing them through a bounded [`Semaphore`]-backed worker pool. Probe
+    /// failures are collected per file rather than aborting the scan.
+    pub async fn scan_directory_reported(&self, path: &Path) -> Result<ScanReport> {
+        let mut candidates = Vec::new();
+        self.collect_candidates(path, &mut candidates).await?;
+
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let mut handles = Vec::with_capacity(candidates.len());
+        let hash_content = self.hash_content;
 
+        for candidate in candidates {
+            let semaphore = Arc::clone(&semaphore);
+            handles.push(tokio::spawn(async move {
+                // Hold a permit for the duration of the probe so no more than
+                // `concurrency` ffprobe processes run at once.
+                let _permit = semaphore.acquire_owned().await;
+                let mut media_file = MediaFile::new(candidate);
+                if hash_content {
+                    match crate::checksum::hash_file(&media_file.path) {
+                        Ok(hash) => media_file.content_hash = Some(hash),
+                        Err(e) => log::warn!(
+                            "Could not hash {}: {}",
+                            media_file.path.display(),
+                            e
+                        ),
+                    }
+                }
+                // Non-video files are classified but not handed to ffprobe;
+                // the caller reports/skips them separately.
+                if media_file.file_type != MediaFileType::Video {
+                    return (media_file, None);
+                }
+                match MediaMetadata::from_file(&media_file.path).await {
+                    Ok(metadata) => {
+                        media_file.metadata = Some(metadata);
+                        (media_file, None)
+                    }
+                    Err(e) => {
+                        let error = ScanError {
+                            path: media_file.path.clone(),
+                            message: e.to_string(),
+                        };
+                        log::warn!("Could not get metadata for {}: {}", media_file.path.display(), e);
+                        (media_file, Some(error))
+                    }
+                }
+            }));
+        }
+
+        let mut report = ScanReport::default();
+        for handle in handles {
+            let (media_file, error) = handle
+                .await
+                .map_err(|e| crate::error::MediaManagerError::Unknown(e.to_string()))?;
+            if let Some(error) = error {
+                report.errors.push(error);
+            }
+            // Video files carry probe-able metadata; everything else is
+            // classified and reported separately rather than silently dropped.
+            if media_file.file_type == MediaFileType::Video {
+                report.files.push(media_file);
+            } else {
+                report.skipped.push(media_file);
+            }
+        }
+
+        // Concurrency reorders results; restore a stable, path-sorted order.
+        report.files.sort_by(|a, b| a.path.cmp(&b.path));
+        report.skipped.sort_by(|a, b| a.path.cmp(&b.path));
+        report.errors.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(report)
+    }
+
+    /// Recursively gathers the paths of every regular file, leaving
+    /// classification (video/audio/image/unknown) to [`MediaFile::new`].
+    #[async_recursion]
+    async fn collect_candidates(&self, path: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+        let mut entries = fs::read_dir(path).await?;
         while let Some(entry) = entries.next_entry().await? {
             let entry_path = entry.path();
             if entry_path.is_dir() {
-                // Recursively scan subdirectories
-                media_files.extend(self.scan_directory(&entry_path).await?);
+                self.collect_candidates(&entry_path, out).await?;
             } else if entry_path.is_file() {
-                if let Some(ext) = entry_path.extension().and_then(|s| s.to_str()) {
-                    if self.allowed_extensions.contains(&ext.to_lowercase()) {
-                        let mut media_file = MediaFile::new(entry_path);
-                        // Attempt to get metadata, but don't fail if it doesn't work
-                        match MediaMetadata::from_file(&media_file.path).await {
-                            Ok(metadata) => media_file.metadata = Some(metadata),
-                            Err(e) => {
-                                log::warn!("Could not get metadata for {}: {}", media_file.path.display(), e);
-                                // Continue without metadata
-                            }
-                        }
-                        media_files.push(media_file);
-                    }
-                }
+                out.push(entry_path);
             }
         }
-        Ok(media_files)
+        Ok(())
     }
 }
+
+/// Default probe concurrency: the number of available CPUs, falling back to a
+/// small fixed pool when that can't be determined.
+pub(crate) fn default_concurrency() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+}
@@ -1,6 +1,17 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use tokio::process::Command;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use crate::config::Config;
+use crate::error::{MediaManagerError, Result};
+use crate::metadata::{MediaMetadata, SubtitleStream};
+
+/// Text-based subtitle codecs that ffmpeg can convert into a `.srt` sidecar.
+const TEXT_CODECS: &[&str] = &["srt", "subrip", "ass", "mov_text"];
+
+/// Image-based subtitle codecs that cannot be converted to SRT.
+const IMAGE_CODECS: &[&str] = &["pgs", "dvd_subtitle", "hdmv_pgs_subtitle"];
 
 #[derive(Error, Debug)]
 pub enum SubtitleError {
@@ -16,6 +27,17 @@ pub struct SubtitleMetadata {
     pub codec: String,
 }
 
+/// Result of extracting embedded subtitle streams to sidecar files.
+///
+/// `extracted` holds the `.srt` files written to disk; `skipped` holds the
+/// streams that were not converted: image-based ones and any whose codec is
+/// not a known text codec.
+#[derive(Debug, Default)]
+pub struct SubtitleExtraction {
+    pub extracted: Vec<PathBuf>,
+    pub skipped: Vec<SubtitleStream>,
+}
+
 /// A simplified subtitle manager that works with embedded subtitle streams
 /// detected during the scan phase. This replaces the previous API-dependent implementation.
 #[derive(Debug)]
@@ -36,6 +58,101 @@ impl SubtitleManager {
         Ok(Vec::new())
     }
 
+    /// Demuxes the text-based subtitle streams detected for `video_path` into
+    /// `.srt` sidecar files under `out_dir`, mirroring the `ffprobe`
+    /// integration in [`MediaMetadata`].
+    ///
+    /// Each sidecar is named `<stem>.<lang>.srt`, using the stream's normalized
+    /// language code, else its raw language tag, else `und`, and a numeric
+    /// suffix when two streams share a language. Image-based streams that
+    /// cannot be converted to SRT, and streams with an unrecognized codec, are
+    /// returned in [`SubtitleExtraction::skipped`] rather than erroring.
+    pub async fn extract_subtitles(
+        &self,
+        video_path: &Path,
+        out_dir: &Path,
+    ) -> Result<SubtitleExtraction> {
+        MediaMetadata::check_ffmpeg().await?;
+
+        let metadata = MediaMetadata::from_file(video_path).await?;
+        let stem = video_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("subtitles")
+            .to_string();
+
+        let mut result = SubtitleExtraction::default();
+        // Track how many sidecars we've written per language so we can add a
+        // numeric suffix when several streams share one language.
+        let mut lang_counts: HashMap<String, u32> = HashMap::new();
+        // `-map 0:s:N` selects the Nth subtitle stream, so we enumerate the
+        // detected subtitle streams rather than using their absolute index.
+        let mut sub_ordinal = 0u32;
+
+        for stream in &metadata.subtitle_streams {
+            let codec = stream.codec.to_lowercase();
+            if IMAGE_CODECS.contains(&codec.as_str()) {
+                result.skipped.push(stream.clone());
+                sub_ordinal += 1;
+                continue;
+            }
+            if !TEXT_CODECS.contains(&codec.as_str()) {
+                // Unknown codec: leave it alone rather than guess at SRT.
+                result.skipped.push(stream.clone());
+                sub_ordinal += 1;
+                continue;
+            }
+
+            // Prefer the normalized two-letter code, falling back to the raw
+            // tag and finally to `und`.
+            let lang = stream
+                .lang_code
+                .clone()
+                .or_else(|| stream.language.clone())
+                .unwrap_or_else(|| "und".to_string());
+            let count = lang_counts.entry(lang.clone()).or_insert(0);
+            let out_name = if *count == 0 {
+                format!("{}.{}.srt", stem, lang)
+            } else {
+                format!("{}.{}.{}.srt", stem, lang, count)
+            };
+            *count += 1;
+            let out_path = out_dir.join(out_name);
+
+            let output = Command::new("ffmpeg")
+                .arg("-v")
+                .arg("quiet")
+                .arg("-i")
+                .arg(video_path)
+                .arg("-map")
+                .arg(format!("0:s:{}", sub_ordinal))
+                .arg("-c:s")
+                .arg("srt")
+                .arg(&out_path)
+                .output()
+                .await
+                .map_err(|e| {
+                    if e.kind() == std::io::ErrorKind::NotFound {
+                        MediaManagerError::FfmpegNotFound
+                    } else {
+                        MediaManagerError::Io(e)
+                    }
+                })?;
+
+            if output.status.success() {
+                result.extracted.push(out_path);
+            } else {
+                return Err(MediaManagerError::FfmpegError(
+                    String::from_utf8_lossy(&output.stderr).into_owned(),
+                ));
+            }
+
+            sub_ordinal += 1;
+        }
+
+        Ok(result)
+    }
+
     /// Extracts IMDB ID from a filename if present (simplified version)
     pub fn extract_imdb_id(filename: &str) -> Option<String> {
         // Simple string-based IMDB ID extraction (e.g., "Movie.Title.tt1234567.mkv")
@@ -54,13 +171,54 @@ impl SubtitleManager {
         None
     }
 
-    /// Generates an OpenSubtitles search URL for a video file (simplified)
+    /// Generates an OpenSubtitles search URL for a video file without online
+    /// enrichment: it uses the IMDB id in the filename when there is one, and
+    /// otherwise searches by the file stem.
     pub fn get_opensubtitles_url(video_path: &Path) -> String {
+        Self::opensubtitles_url_for(video_path, None)
+    }
+
+    /// Resolves an OpenSubtitles search URL for `video_path`.
+    ///
+    /// With the `online` feature enabled and an API key configured, the
+    /// filename is parsed and enriched to recover an IMDB id — so lookups work
+    /// even for files whose names lack a `tt` id. Otherwise, and on any
+    /// enrichment miss or failure, this degrades to the offline,
+    /// filename-based URL.
+    pub async fn resolve_opensubtitles_url(&self, video_path: &Path, _config: &Config) -> String {
+        #[cfg(feature = "online")]
+        {
+            if let Some(api_key) = _config.api_key() {
+                let stem = video_path.file_stem().and_then(|n| n.to_str()).unwrap_or("");
+                let parsed = crate::parser::ParsedTitle::from_filename(stem);
+                let enricher = crate::enrichment::MetadataEnricher::new(api_key);
+                match enricher.enrich(&parsed).await {
+                    Ok(Some(meta)) => {
+                        if let Some(imdb_id) = meta.imdb_id.as_deref() {
+                            return Self::opensubtitles_url_for(video_path, Some(imdb_id));
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::warn!("Online enrichment failed for {}: {}", video_path.display(), e),
+                }
+            }
+        }
+        Self::opensubtitles_url_for(video_path, None)
+    }
+
+    /// Builds an OpenSubtitles URL, preferring an explicitly resolved IMDB id
+    /// and otherwise falling back to one parsed from the filename, then to a
+    /// movie-name search.
+    fn opensubtitles_url_for(video_path: &Path, imdb_id: Option<&str>) -> String {
         let filename = video_path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("");
-            
-        if let Some(imdb_id) = Self::extract_imdb_id(filename) {
+
+        let resolved = imdb_id
+            .map(|id| id.to_string())
+            .or_else(|| Self::extract_imdb_id(filename));
+
+        if let Some(imdb_id) = resolved {
             format!("https://www.opensubtitles.org/en/search/sublanguageid-all/imdbid-{}", imdb_id)
         } else {
             let movie_name = video_path.file_stem()
@@ -0,0 +1,228 @@
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::process::Command;
+
+use crate::error::{MediaManagerError, Result};
+use crate::media_file::MediaFile;
+use crate::metadata::MediaMetadata;
+use crate::renamer::MediaRenamer;
+
+/// Image format a thumbnail is encoded as, mapping to the ffmpeg codec and
+/// file extension used for output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailFormat {
+    Jpeg,
+    Webp,
+}
+
+impl ThumbnailFormat {
+    /// The `-c:v` codec name ffmpeg uses to encode this format.
+    pub fn codec(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "mjpeg",
+            ThumbnailFormat::Webp => "libwebp",
+        }
+    }
+
+    /// The file extension for this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Jpeg => "jpg",
+            ThumbnailFormat::Webp => "webp",
+        }
+    }
+}
+
+/// Extracts still frames from video files via `ffmpeg`, mirroring the ffprobe
+/// integration in [`MediaMetadata`].
+///
+/// Output names are derived from the source through the same template engine
+/// used for renames, so thumbnails line up with their videos.
+pub struct Thumbnailer {
+    renamer: MediaRenamer,
+}
+
+impl Thumbnailer {
+    /// Creates a new thumbnailer.
+    pub fn new() -> Result<Self> {
+        Ok(Thumbnailer {
+            renamer: MediaRenamer::new()?,
+        })
+    }
+
+    /// Grabs a single frame at `fraction` of the video's duration (e.g. `0.25`
+    /// for the 25% mark) and writes it to `out_dir`, named via `template`.
+    pub async fn single_frame(
+        &self,
+        video_path: &Path,
+        out_dir: &Path,
+        template: &str,
+        format: ThumbnailFormat,
+        fraction: f64,
+    ) -> Result<PathBuf> {
+        MediaMetadata::check_ffmpeg().await?;
+
+        let timestamp = self.seek_timestamp(video_path, fraction).await?;
+        let out_path = self.output_path(video_path, out_dir, template, format).await?;
+
+        let status = Command::new("ffmpeg")
+            .arg("-v")
+            .arg("quiet")
+            .arg("-ss")
+            .arg(format!("{:.3}", timestamp))
+            .arg("-i")
+            .arg(video_path)
+            .arg("-frames:v")
+            .arg("1")
+            .arg("-f")
+            .arg("image2")
+            .arg("-c:v")
+            .arg(format.codec())
+            .arg("-y")
+            .arg(&out_path)
+            .status()
+            .await
+            .map_err(map_ffmpeg_spawn)?;
+
+        if !status.success() {
+            return Err(MediaManagerError::FfmpegError(format!(
+                "failed to extract frame from {}",
+                video_path.display()
+            )));
+        }
+
+        Ok(out_path)
+    }
+
+    /// Extracts `count` evenly spaced frames and tiles them into a single
+    /// contact sheet written to `out_dir`.
+    pub async fn contact_sheet(
+        &self,
+        video_path: &Path,
+        out_dir: &Path,
+        template: &str,
+        format: ThumbnailFormat,
+        count: usize,
+    ) -> Result<PathBuf> {
+        MediaMetadata::check_ffmpeg().await?;
+        if count == 0 {
+            return Err(MediaManagerError::Unknown(
+                "contact sheet frame count must be greater than zero".to_string(),
+            ));
+        }
+
+        let metadata = MediaMetadata::from_file(video_path).await?;
+        let duration = metadata.duration_seconds.unwrap_or(0.0);
+
+        // Stage the individual frames in a per-process temp directory.
+        let frame_dir = std::env::temp_dir().join(format!(
+            "media_manager_sheet_{}_{}",
+            std::process::id(),
+            video_path.file_stem().and_then(|s| s.to_str()).unwrap_or("thumb")
+        ));
+        fs::create_dir_all(&frame_dir).await?;
+
+        for i in 0..count {
+            // Space the frames across the runtime, avoiding the very ends.
+            let fraction = (i as f64 + 1.0) / (count as f64 + 1.0);
+            let timestamp = duration * fraction;
+            let frame_path = frame_dir.join(format!("frame_{:03}.{}", i + 1, format.extension()));
+            let status = Command::new("ffmpeg")
+                .arg("-v")
+                .arg("quiet")
+                .arg("-ss")
+                .arg(format!("{:.3}", timestamp))
+                .arg("-i")
+                .arg(video_path)
+                .arg("-frames:v")
+                .arg("1")
+                .arg("-f")
+                .arg("image2")
+                .arg("-c:v")
+                .arg(format.codec())
+                .arg("-y")
+                .arg(&frame_path)
+                .status()
+                .await
+                .map_err(map_ffmpeg_spawn)?;
+            if !status.success() {
+                return Err(MediaManagerError::FfmpegError(format!(
+                    "failed to extract contact-sheet frame {} from {}",
+                    i + 1,
+                    video_path.display()
+                )));
+            }
+        }
+
+        // Tile the frames into a grid as close to square as possible.
+        let cols = (count as f64).sqrt().ceil() as usize;
+        let rows = count.div_ceil(cols);
+        let out_path = self.output_path(video_path, out_dir, template, format).await?;
+        let pattern = frame_dir.join(format!("frame_%03d.{}", format.extension()));
+
+        let status = Command::new("ffmpeg")
+            .arg("-v")
+            .arg("quiet")
+            .arg("-i")
+            .arg(&pattern)
+            .arg("-vf")
+            .arg(format!("tile={}x{}", cols, rows))
+            .arg("-frames:v")
+            .arg("1")
+            .arg("-c:v")
+            .arg(format.codec())
+            .arg("-y")
+            .arg(&out_path)
+            .status()
+            .await
+            .map_err(map_ffmpeg_spawn)?;
+
+        // Best-effort cleanup of the staging directory.
+        let _ = fs::remove_dir_all(&frame_dir).await;
+
+        if !status.success() {
+            return Err(MediaManagerError::FfmpegError(format!(
+                "failed to tile contact sheet for {}",
+                video_path.display()
+            )));
+        }
+
+        Ok(out_path)
+    }
+
+    /// Resolves the seek timestamp for a single-frame grab, clamping the
+    /// fraction to `[0, 1)` and falling back to the start when the duration is
+    /// unknown.
+    async fn seek_timestamp(&self, video_path: &Path, fraction: f64) -> Result<f64> {
+        let metadata = MediaMetadata::from_file(video_path).await?;
+        let duration = metadata.duration_seconds.unwrap_or(0.0);
+        let fraction = fraction.clamp(0.0, 0.999);
+        Ok(duration * fraction)
+    }
+
+    /// Builds the output path by running the source filename through the
+    /// template engine and appending the format's extension.
+    async fn output_path(
+        &self,
+        video_path: &Path,
+        out_dir: &Path,
+        template: &str,
+        format: ThumbnailFormat,
+    ) -> Result<PathBuf> {
+        let mut media_file = MediaFile::new(video_path.to_path_buf());
+        if let Ok(metadata) = MediaMetadata::from_file(video_path).await {
+            media_file.metadata = Some(metadata);
+        }
+        let stem = self.renamer.apply_template(&media_file, template, 1, 1)?;
+        Ok(out_dir.join(format!("{}.{}", stem, format.extension())))
+    }
+}
+
+/// Maps a spawn error from launching ffmpeg to the right crate error.
+fn map_ffmpeg_spawn(e: std::io::Error) -> MediaManagerError {
+    if e.kind() == std::io::ErrorKind::NotFound {
+        MediaManagerError::FfmpegNotFound
+    } else {
+        MediaManagerError::Io(e)
+    }
+}
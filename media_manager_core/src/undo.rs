@@ -3,6 +3,9 @@ use tokio::fs;
 use serde::{Deserialize, Serialize};
 use crate::error::Result;
 
+/// Default number of rename batches kept on the undo/redo stack.
+pub const DEFAULT_MAX_DEPTH: usize = 10;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RenameOperation {
     pub original_path: PathBuf,
@@ -14,45 +17,129 @@ pub struct UndoData {
     pub operations: Vec<RenameOperation>,
 }
 
+/// Bounded undo/redo stack persisted to disk.
+///
+/// `batches[..cursor]` are the batches that are currently applied (undoable);
+/// `batches[cursor..]` are batches that have been undone and can be redone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoHistory {
+    pub batches: Vec<UndoData>,
+    pub cursor: usize,
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        UndoHistory {
+            batches: Vec::new(),
+            cursor: 0,
+        }
+    }
+}
+
 pub struct UndoManager {
     undo_file_path: PathBuf,
+    max_depth: usize,
 }
 
 impl UndoManager {
     pub fn new() -> Result<Self> {
+        Self::new_with_depth(DEFAULT_MAX_DEPTH)
+    }
+
+    /// Creates an `UndoManager` that keeps at most `max_depth` batches.
+    /// A value of zero is treated as one.
+    pub fn new_with_depth(max_depth: usize) -> Result<Self> {
         // Create a .media_manager directory in the current directory
         let mut undo_dir = std::env::current_dir()?;
         undo_dir.push(".media_manager");
         if !undo_dir.exists() {
             std::fs::create_dir(&undo_dir)?;
         }
-        
+
         let mut undo_file_path = undo_dir;
         undo_file_path.push("undo_data.json");
-        
-        Ok(UndoManager { undo_file_path })
+
+        Ok(UndoManager {
+            undo_file_path,
+            max_depth: max_depth.max(1),
+        })
+    }
+
+    /// Loads the undo history from disk, migrating the old single-batch format
+    /// (`{ "operations": [...] }`) into a one-element stack if encountered.
+    pub async fn load_history(&self) -> Result<UndoHistory> {
+        if !self.undo_file_path.exists() {
+            return Ok(UndoHistory::default());
+        }
+        let json_data = fs::read_to_string(&self.undo_file_path).await?;
+
+        // Prefer the current format; fall back to the legacy single object.
+        if let Ok(history) = serde_json::from_str::<UndoHistory>(&json_data) {
+            return Ok(history);
+        }
+        let legacy: UndoData = serde_json::from_str(&json_data)?;
+        log::info!("Migrated legacy undo data into history stack.");
+        Ok(UndoHistory {
+            batches: vec![legacy],
+            cursor: 1,
+        })
     }
 
-    /// Saves the current batch of rename operations for undo.
-    pub async fn save_undo_data(&self, data: &UndoData) -> Result<()> {
-        let json_data = serde_json::to_string_pretty(data)?;
+    /// Persists the undo history to disk.
+    pub async fn save_history(&self, history: &UndoHistory) -> Result<()> {
+        let json_data = serde_json::to_string_pretty(history)?;
         fs::write(&self.undo_file_path, json_data).await?;
-        log::info!("Undo data saved to: {}", self.undo_file_path.display());
+        log::info!("Undo history saved to: {}", self.undo_file_path.display());
         Ok(())
     }
 
-    /// Loads the last saved undo data.
-    pub async fn load_undo_data(&self) -> Result<Option<UndoData>> {
-        if !self.undo_file_path.exists() {
+    /// Pushes a freshly committed batch onto the stack. Any previously undone
+    /// batches (redo candidates) are discarded, and the stack is trimmed to
+    /// `max_depth` by dropping the oldest batches.
+    pub async fn push_batch(&self, data: UndoData) -> Result<()> {
+        let mut history = self.load_history().await?;
+        // Committing a new batch invalidates the redo branch.
+        history.batches.truncate(history.cursor);
+        history.batches.push(data);
+
+        if history.batches.len() > self.max_depth {
+            let overflow = history.batches.len() - self.max_depth;
+            history.batches.drain(0..overflow);
+        }
+        history.cursor = history.batches.len();
+
+        self.save_history(&history).await
+    }
+
+    /// Moves the cursor back one step and returns the batch that should be
+    /// reversed (new_path -> original_path), or `None` if there is nothing
+    /// left to undo.
+    pub async fn pop_undo(&self) -> Result<Option<UndoData>> {
+        let mut history = self.load_history().await?;
+        if history.cursor == 0 {
             return Ok(None);
         }
-        let json_data = fs::read_to_string(&self.undo_file_path).await?;
-        let data: UndoData = serde_json::from_str(&json_data)?;
-        log::info!("Undo data loaded from: {}", self.undo_file_path.display());
-        Ok(Some(data))
+        history.cursor -= 1;
+        let batch = history.batches[history.cursor].clone();
+        self.save_history(&history).await?;
+        Ok(Some(batch))
+    }
+
+    /// Moves the cursor forward one step and returns the batch that should be
+    /// re-applied in the forward direction, or `None` if there is nothing to
+    /// redo.
+    pub async fn pop_redo(&self) -> Result<Option<UndoData>> {
+        let mut history = self.load_history().await?;
+        if history.cursor >= history.batches.len() {
+            return Ok(None);
+        }
+        let batch = history.batches[history.cursor].clone();
+        history.cursor += 1;
+        self.save_history(&history).await?;
+        Ok(Some(batch))
     }
 
-    /// Clears the undo data.
+    /// Clears the entire undo history.
     pub async fn clear_undo_data(&self) -> Result<()> {
         if self.undo_file_path.exists() {
             fs::remove_file(&self.undo_file_path).await?;
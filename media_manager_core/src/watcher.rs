@@ -0,0 +1,189 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::fs;
+use tokio::time::sleep;
+
+use crate::error::Result;
+use crate::media_file::{MediaFile, MediaFileType};
+use crate::metadata::MediaMetadata;
+use crate::renamer::MediaRenamer;
+
+/// Video extensions the watcher tracks, mirroring the video extensions in
+/// [`MediaFile::new`].
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mkv", "avi", "mov", "webm"];
+
+/// An observable change surfaced by [`MediaWatcher::watch`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WatchEvent {
+    /// A new, fully-written video file appeared (no template given, or the
+    /// template produced no usable new name).
+    Detected { path: PathBuf },
+    /// A newly detected file was auto-renamed against the supplied template.
+    Renamed { from: PathBuf, to: PathBuf },
+}
+
+/// Keeps a directory under observation, re-probing only the files that change
+/// and optionally renaming new files against a template. Renames are applied
+/// on disk as soon as a file is stable; they are not staged for `commit` and
+/// are not recorded in the undo history.
+///
+/// The watched directory is resolved against the process's initial working
+/// directory once, at construction time, and the resulting absolute path is
+/// used for every poll — so a later `chdir` by the process does not break the
+/// watcher.
+pub struct MediaWatcher {
+    dir: PathBuf,
+    /// Coalesce window: bursts of activity within one interval are collapsed,
+    /// and a file counts as "done being written" once its size is unchanged
+    /// across two consecutive polls spaced this far apart.
+    poll_interval: Duration,
+}
+
+impl MediaWatcher {
+    /// Creates a watcher for `dir`, resolving a relative path against the
+    /// current working directory immediately.
+    pub fn new(dir: &Path) -> Result<Self> {
+        let dir = if dir.is_absolute() {
+            dir.to_path_buf()
+        } else {
+            std::env::current_dir()?.join(dir)
+        };
+        Ok(MediaWatcher {
+            dir,
+            poll_interval: Duration::from_millis(200),
+        })
+    }
+
+    /// Returns the absolute directory being watched.
+    pub fn directory(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Watches the directory indefinitely, invoking `on_event` for each newly
+    /// detected (and optionally renamed) file. When `template` is supplied,
+    /// each stable new file is renamed on disk against it.
+    pub async fn watch<F>(&self, template: Option<&str>, mut on_event: F) -> Result<()>
+    where
+        F: FnMut(WatchEvent),
+    {
+        let renamer = MediaRenamer::new()?;
+        // Last observed size per path; used to detect growth (still writing).
+        let mut sizes: HashMap<PathBuf, u64> = HashMap::new();
+        // Paths we've already acted on, so we don't re-report a steady file.
+        let mut reported: HashSet<PathBuf> = HashSet::new();
+
+        loop {
+            let current = collect_video_files(&self.dir).await?;
+            let present: HashSet<PathBuf> = current.iter().map(|(p, _)| p.clone()).collect();
+
+            // Forget files that have disappeared so they can be re-detected.
+            // Done before processing so rename targets recorded below survive
+            // until the next poll sees them.
+            sizes.retain(|p, _| present.contains(p));
+            reported.retain(|p| present.contains(p));
+
+            for (path, size) in &current {
+                let stable = sizes.get(path) == Some(size);
+                sizes.insert(path.clone(), *size);
+
+                if !stable {
+                    // New or still growing: wait for the next poll to confirm.
+                    reported.remove(path);
+                    continue;
+                }
+                if reported.contains(path) {
+                    continue;
+                }
+
+                self.process_file(&renamer, template, path, *size, &mut sizes, &mut reported, &mut on_event)
+                    .await?;
+            }
+
+            sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Probes a stable new file and either renames it against `template` or
+    /// reports it as detected.
+    #[allow(clippy::too_many_arguments)]
+    async fn process_file<F>(
+        &self,
+        renamer: &MediaRenamer,
+        template: Option<&str>,
+        path: &Path,
+        size: u64,
+        sizes: &mut HashMap<PathBuf, u64>,
+        reported: &mut HashSet<PathBuf>,
+        on_event: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(WatchEvent),
+    {
+        let mut media_file = MediaFile::new(path.to_path_buf());
+        if media_file.file_type == MediaFileType::Video {
+            if let Ok(metadata) = MediaMetadata::from_file(&media_file.path).await {
+                media_file.metadata = Some(metadata);
+            }
+        }
+
+        if let Some(template) = template {
+            let preview = renamer.preview_rename(&media_file, template, 1, 1)?;
+            if preview.is_valid && preview.new_path != media_file.path {
+                fs::rename(&media_file.path, &preview.new_path).await?;
+                // Track the target so it isn't re-detected as a fresh file.
+                sizes.insert(preview.new_path.clone(), size);
+                reported.insert(preview.new_path.clone());
+                reported.insert(path.to_path_buf());
+                on_event(WatchEvent::Renamed {
+                    from: media_file.path.clone(),
+                    to: preview.new_path,
+                });
+                return Ok(());
+            }
+        }
+
+        reported.insert(path.to_path_buf());
+        on_event(WatchEvent::Detected {
+            path: path.to_path_buf(),
+        });
+        Ok(())
+    }
+}
+
+/// Recursively collects `(path, size)` for every video file under `dir`.
+async fn collect_video_files(dir: &Path) -> Result<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        let mut entries = match fs::read_dir(&current).await {
+            Ok(entries) => entries,
+            // A directory vanishing mid-walk is expected in a live tree.
+            Err(_) => continue,
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                stack.push(entry_path);
+            } else if is_video_file(&entry_path) {
+                if let Ok(meta) = fs::metadata(&entry_path).await {
+                    files.push((entry_path, meta.len()));
+                }
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Returns true when a path has a known video extension.
+fn is_video_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}